@@ -0,0 +1,6 @@
+pub mod parser;
+pub mod printer;
+pub mod value;
+
+pub use parser::{json, parse, parse_strict, JsonValue, ParseError};
+pub use printer::to_pretty_string;
@@ -1,5 +1,3 @@
-mod parser;
-
 fn main() {
-    println!("{:?}", parser::json("{\"name\":\"lw\"}"));
+    println!("{:?}", json_parser::parse("{\"name\":\"lw\"}"));
 }
@@ -1,55 +1,288 @@
 use std::collections::HashMap;
+use std::fmt;
 
 use nom::{
     branch::alt,
-    bytes::complete::{escaped, tag, take_till1, take_while_m_n},
-    character::complete::multispace0,
-    combinator::{map, peek},
-    error::context,
-    multi::separated_list0,
-    number::complete::double,
-    sequence::{delimited, preceded, separated_pair},
-    IResult,
+    bytes::complete::{tag, take_till1, take_while_m_n},
+    character::complete::{char, digit0, digit1, multispace0, one_of},
+    combinator::{all_consuming, consumed, cut, map, opt, recognize},
+    error::{context, ContextError, Error, ErrorKind, FromExternalError},
+    multi::many0,
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    Err, IResult, Offset,
 };
 
-#[derive(Debug)]
+//数组和对象允许嵌套的最大层数
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, Clone)]
 pub enum JsonValue {
     Str(String),
     Boolean(bool),
     Null,
+    Int(i64),
     Num(f64),
     Array(Vec<JsonValue>),
-    Object(HashMap<String, JsonValue>),
+    //按键在文本中出现的顺序保存成员
+    Object(Vec<(String, JsonValue)>),
+}
+
+//解析失败时的错误信息，offset为停止解析处的字节偏移
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub offset: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at offset {}", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+//解析过程中的错误，在nom的错误类型之外记录json特有的错误
+#[derive(Debug)]
+struct JsonError<'a> {
+    input: &'a str,
+    kind: JsonErrorKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum JsonErrorKind {
+    Nom(ErrorKind),
+    ExpectedValue,
+    Expected(&'static str),
+    InvalidEscape,
+    InvalidUnicode,
+    ControlCharacter,
+    NumberOutOfRange,
+    RecursionLimit,
+    DuplicateKey,
+}
+
+type Res<'a, O> = IResult<&'a str, O, JsonError<'a>>;
+
+impl<'a> JsonError<'a> {
+    fn new(input: &'a str, kind: JsonErrorKind) -> Self {
+        JsonError { input, kind }
+    }
+
+    //转换为nom的错误类型，json特有的错误使用最接近的ErrorKind
+    fn into_nom(self) -> Error<&'a str> {
+        let code = match self.kind {
+            JsonErrorKind::Nom(code) => code,
+            JsonErrorKind::ExpectedValue => ErrorKind::Alt,
+            JsonErrorKind::Expected(_) => ErrorKind::Tag,
+            JsonErrorKind::InvalidEscape | JsonErrorKind::InvalidUnicode => ErrorKind::Escaped,
+            JsonErrorKind::ControlCharacter => ErrorKind::Char,
+            JsonErrorKind::NumberOutOfRange => ErrorKind::Float,
+            JsonErrorKind::RecursionLimit => ErrorKind::TooLarge,
+            JsonErrorKind::DuplicateKey => ErrorKind::Verify,
+        };
+        Error::new(self.input, code)
+    }
+
+    //转换为对外的错误，input为完整的输入文本
+    fn into_parse_error(self, input: &str) -> ParseError {
+        let message = match self.kind {
+            _ if self.input.is_empty() => String::from("unexpected end of input"),
+            JsonErrorKind::Nom(ErrorKind::Eof) => String::from("unexpected trailing characters"),
+            JsonErrorKind::Nom(_) => String::from("invalid json"),
+            JsonErrorKind::ExpectedValue => String::from("expected value"),
+            JsonErrorKind::Expected(token) => format!("expected {}", token),
+            JsonErrorKind::InvalidEscape => String::from("invalid escape sequence in string"),
+            JsonErrorKind::InvalidUnicode => String::from("invalid unicode escape in string"),
+            JsonErrorKind::ControlCharacter => String::from("control character in string"),
+            JsonErrorKind::NumberOutOfRange => String::from("number out of range"),
+            JsonErrorKind::RecursionLimit => String::from("recursion limit exceeded"),
+            JsonErrorKind::DuplicateKey => format!("duplicate key {}", self.input),
+        };
+
+        ParseError {
+            message,
+            offset: input.offset(self.input),
+        }
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for JsonError<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        JsonError::new(input, JsonErrorKind::Nom(kind))
+    }
+
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<'a> ContextError<&'a str> for JsonError<'a> {}
+
+impl<'a, E> FromExternalError<&'a str, E> for JsonError<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _: E) -> Self {
+        JsonError::new(input, JsonErrorKind::Nom(kind))
+    }
+}
+
+//解析失败时以kind替换错误，不再回溯的错误保持不变
+fn expect<'a, O, F>(kind: JsonErrorKind, mut parser: F) -> impl FnMut(&'a str) -> Res<'a, O>
+where
+    F: FnMut(&'a str) -> Res<'a, O>,
+{
+    move |i| {
+        parser(i).map_err(|e| match e {
+            Err::Error(_) => Err::Error(JsonError::new(i, kind)),
+            e => e,
+        })
+    }
+}
+
+//解析数字，格式为-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?
+fn num(i: &str) -> Res<'_, JsonValue> {
+    let (rest, s) = context(
+        "num",
+        recognize(tuple((
+            opt(char('-')),
+            alt((tag("0"), recognize(pair(one_of("123456789"), digit0)))),
+            opt(pair(char('.'), digit1)),
+            opt(tuple((one_of("eE"), opt(one_of("+-")), digit1))),
+        ))),
+    )(i)?;
+
+    match to_number(s) {
+        Some(value) => Ok((rest, value)),
+        None => Err(Err::Failure(JsonError::new(
+            i,
+            JsonErrorKind::NumberOutOfRange,
+        ))),
+    }
 }
 
-//解析数字
-fn num(i: &str) -> IResult<&str, JsonValue> {
-    context("num", map(double, |s| JsonValue::Num(s)))(i)
+//不含小数和指数且在i64范围内时为整数，否则为浮点数，-0按浮点数保留符号
+//json无法表示的无穷视为超出范围
+fn to_number(s: &str) -> Option<JsonValue> {
+    if s != "-0" && !s.contains(&['.', 'e', 'E'][..]) {
+        if let Ok(n) = s.parse() {
+            return Some(JsonValue::Int(n));
+        }
+    }
+
+    s.parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite())
+        .map(JsonValue::Num)
 }
 
 //解析str
-fn string(i: &str) -> IResult<&str, JsonValue> {
-    context(
-        "string",
-        map(
-            alt((tag("\"\""), delimited(tag("\""), parse_str, tag("\"")))),
-            |s| JsonValue::Str(String::from(s)),
-        ),
-    )(i)
+fn string(i: &str) -> Res<'_, JsonValue> {
+    context("string", map(quoted_string, JsonValue::Str))(i)
+}
+
+//解析字符串并还原转义，错误位置指向无法还原的转义序列
+fn quoted_string(i: &str) -> Res<'_, String> {
+    let (rest, raw) = quoted(i)?;
+
+    match unescape(raw) {
+        Ok(s) => Ok((rest, s)),
+        Err(pos) => Err(Err::Failure(JsonError::new(
+            &raw[pos..],
+            JsonErrorKind::InvalidUnicode,
+        ))),
+    }
+}
+
+//解析引号包裹的内容，不含引号，以引号开头后的错误不再回溯
+fn quoted(i: &str) -> Res<'_, &str> {
+    preceded(tag("\""), cut(terminated(parse_str, closing_quote)))(i)
 }
 
 //解析含有转义字符的字符串
-fn parse_str(i: &str) -> IResult<&str, &str> {
-    context("parse_str", escaped(normal, '\\', escapable))(i)
+fn parse_str(i: &str) -> Res<'_, &str> {
+    context("parse_str", recognize(many0(alt((normal, escape)))))(i)
+}
+
+//解析结束的引号，字符串中不允许出现未转义的控制字符
+fn closing_quote(i: &str) -> Res<'_, &str> {
+    match i.chars().next() {
+        Some(c) if c.is_ascii_control() => Err(Err::Failure(JsonError::new(
+            i,
+            JsonErrorKind::ControlCharacter,
+        ))),
+        _ => expect(JsonErrorKind::Expected("'\"'"), tag("\""))(i),
+    }
+}
+
+//将转义序列还原为实际字符，无法表示的码点返回该转义序列的字节位置
+fn unescape(s: &str) -> Result<String, usize> {
+    let mut res = String::with_capacity(s.len());
+    let mut chars = s.char_indices();
+
+    while let Some((pos, c)) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some((_, 'b')) => res.push('\u{8}'),
+            Some((_, 'f')) => res.push('\u{c}'),
+            Some((_, 'n')) => res.push('\n'),
+            Some((_, 'r')) => res.push('\r'),
+            Some((_, 't')) => res.push('\t'),
+            Some((_, 'u')) => {
+                let mut code = hex_code(&mut chars).ok_or(pos)?;
+
+                //高位代理项后必须紧跟低位代理项
+                if (0xD800..=0xDBFF).contains(&code) {
+                    let prefix: String = chars.by_ref().take(2).map(|(_, c)| c).collect();
+                    if prefix != "\\u" {
+                        return Err(pos);
+                    }
+                    let low = hex_code(&mut chars).ok_or(pos)?;
+                    if !(0xDC00..=0xDFFF).contains(&low) {
+                        return Err(pos);
+                    }
+                    code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                }
+
+                res.push(std::char::from_u32(code).ok_or(pos)?);
+            }
+            Some((_, c)) => res.push(c),
+            None => return Err(pos),
+        }
+    }
+
+    Ok(res)
+}
+
+//读取\u后的四位十六进制码点
+fn hex_code(chars: &mut std::str::CharIndices) -> Option<u32> {
+    let hex: String = chars.take(4).map(|(_, c)| c).collect();
+    u32::from_str_radix(&hex, 16).ok()
 }
 
 //解析正常字符
-fn normal(i: &str) -> IResult<&str, &str> {
+fn normal(i: &str) -> Res<'_, &str> {
     take_till1(|c: char| c == '\\' || c == '"' || c.is_ascii_control())(i)
 }
 
+//解析转义字符，错误位置指向反斜杠
+fn escape(i: &str) -> Res<'_, &str> {
+    let (rest, _) = char('\\')(i)?;
+
+    escapable(rest).map_err(|_| {
+        let kind = if rest.starts_with('u') {
+            JsonErrorKind::InvalidUnicode
+        } else {
+            JsonErrorKind::InvalidEscape
+        };
+        Err::Failure(JsonError::new(i, kind))
+    })
+}
+
 //解析转义字符后的内容
-fn escapable(i: &str) -> IResult<&str, &str> {
+fn escapable(i: &str) -> Res<'_, &str> {
     context(
         "escapable",
         alt((
@@ -66,89 +299,401 @@ fn escapable(i: &str) -> IResult<&str, &str> {
     )(i)
 }
 
-//解析unicode字符
-fn parse_hex(i: &str) -> IResult<&str, &str> {
+//解析unicode字符，u之后必须是四位十六进制
+fn parse_hex(i: &str) -> Res<'_, &str> {
     context(
         "parse_hex",
-        preceded(
-            peek(tag("u")),
-            take_while_m_n(5, 5, |c: char| c.is_ascii_hexdigit() || c == 'u'),
-        ),
+        recognize(preceded(
+            tag("u"),
+            take_while_m_n(4, 4, |c: char| c.is_ascii_hexdigit()),
+        )),
     )(i)
 }
 
-//解析value
-fn value(i: &str) -> IResult<&str, JsonValue> {
+//解析value，depth为当前所在的嵌套层数，strict为true时拒绝对象中重复的键
+fn value(i: &str, depth: usize, strict: bool) -> Res<'_, JsonValue> {
     context(
         "value",
         delimited(
             multispace0,
-            alt((string, num, boolean, null, array, object)),
+            expect(
+                JsonErrorKind::ExpectedValue,
+                alt((
+                    string,
+                    num,
+                    boolean,
+                    null,
+                    |i| array(i, depth, strict),
+                    |i| object(i, depth, strict),
+                )),
+            ),
             multispace0,
         ),
     )(i)
 }
 
 //解析布尔
-fn boolean(i: &str) -> IResult<&str, JsonValue> {
+fn boolean(i: &str) -> Res<'_, JsonValue> {
     let parse_true = nom::combinator::value(true, tag("true"));
     let parse_false = nom::combinator::value(false, tag("false"));
 
     context(
         "boolean",
-        map(alt((parse_true, parse_false)), |b| JsonValue::Boolean(b)),
+        map(alt((parse_true, parse_false)), JsonValue::Boolean),
     )(i)
 }
 
 //解析null
-fn null(i: &str) -> IResult<&str, JsonValue> {
+fn null(i: &str) -> Res<'_, JsonValue> {
     context("null", map(tag("null"), |_| JsonValue::Null))(i)
 }
 
-//解析array
-fn array(i: &str) -> IResult<&str, JsonValue> {
+//嵌套层数超过限制时报错，避免递归过深导致栈溢出
+fn enter(i: &str, depth: usize) -> Res<'_, ()> {
+    if depth < MAX_DEPTH {
+        Ok((i, ()))
+    } else {
+        Err(Err::Failure(JsonError::new(
+            i,
+            JsonErrorKind::RecursionLimit,
+        )))
+    }
+}
+
+//解析array，"["之后的错误不再回溯
+fn array(i: &str, depth: usize, strict: bool) -> Res<'_, JsonValue> {
     context(
         "array",
         map(
-            delimited(
-                tag("["),
-                separated_list0(tag(","), delimited(multispace0, value, multispace0)),
-                tag("]"),
+            preceded(
+                tuple((tag("["), |i| enter(i, depth), multispace0)),
+                cut(alt((
+                    map(tag("]"), |_| Vec::new()),
+                    terminated(
+                        list(|i| value(i, depth + 1, strict)),
+                        expect(JsonErrorKind::Expected("',' or ']'"), tag("]")),
+                    ),
+                ))),
             ),
-            |res| JsonValue::Array(res),
+            JsonValue::Array,
         ),
     )(i)
 }
 
-fn object(i: &str) -> IResult<&str, JsonValue> {
-    context(
+//解析object，"{"之后的错误不再回溯
+fn object(i: &str, depth: usize, strict: bool) -> Res<'_, JsonValue> {
+    let (rest, vec) = context(
         "object",
-        map(
-            delimited(
-                tag("{"),
-                map(
-                    separated_list0(tag(","), separated_pair(key, tag(":"), value)),
-                    |vec: Vec<(&str, JsonValue)>| {
-                        vec.into_iter().map(|(k, v)| (String::from(k), v)).collect()
-                    },
+        preceded(
+            tuple((tag("{"), |i| enter(i, depth), multispace0)),
+            cut(alt((
+                map(tag("}"), |_| Vec::new()),
+                terminated(
+                    list(|i| member(i, depth + 1, strict)),
+                    expect(JsonErrorKind::Expected("',' or '}'"), tag("}")),
                 ),
-                tag("}"),
-            ),
-            |map: HashMap<String, JsonValue>| JsonValue::Object(map),
+            ))),
         ),
+    )(i)?;
+
+    match members(vec, strict) {
+        Ok(members) => Ok((rest, JsonValue::Object(members))),
+        Err(key) => Err(Err::Failure(JsonError::new(
+            key,
+            JsonErrorKind::DuplicateKey,
+        ))),
+    }
+}
+
+//解析对象成员，同时返回键的原始文本用于报告重复的键，键之后的错误不再回溯
+fn member(i: &str, depth: usize, strict: bool) -> Res<'_, ((&str, String), JsonValue)> {
+    pair(
+        delimited(multispace0, consumed(key), multispace0),
+        cut(preceded(
+            expect(JsonErrorKind::Expected("':'"), tag(":")),
+            |i| value(i, depth, strict),
+        )),
     )(i)
 }
 
-fn key(i: &str) -> IResult<&str, &str> {
+//解析逗号分隔的元素，逗号之后必须跟随元素
+fn list<'a, O, F>(elem: F) -> impl FnMut(&'a str) -> Res<'a, Vec<O>>
+where
+    F: FnMut(&'a str) -> Res<'a, O> + Copy,
+{
+    map(
+        pair(elem, many0(preceded(tag(","), cut(elem)))),
+        |(first, rest)| {
+            let mut vec = vec![first];
+            vec.extend(rest);
+            vec
+        },
+    )
+}
+
+//合并对象成员，重复的键保留首次出现的位置和最后一次的值
+//strict为true时遇到重复的键返回该键的原始文本
+fn members(
+    vec: Vec<((&str, String), JsonValue)>,
+    strict: bool,
+) -> Result<Vec<(String, JsonValue)>, &str> {
+    let mut res: Vec<(String, JsonValue)> = Vec::with_capacity(vec.len());
+    //记录每个键在res中的位置，避免逐个比较
+    let mut index: HashMap<String, usize> = HashMap::with_capacity(vec.len());
+
+    for ((raw, k), v) in vec {
+        match index.get(&k) {
+            Some(_) if strict => return Err(raw),
+            Some(&pos) => res[pos].1 = v,
+            None => {
+                index.insert(k.clone(), res.len());
+                res.push((k, v));
+            }
+        }
+    }
+
+    Ok(res)
+}
+
+fn key(i: &str) -> Res<'_, String> {
     context(
         "key",
-        alt((tag("\"\""), delimited(tag("\""), parse_str, tag("\"")))),
+        expect(JsonErrorKind::Expected("string key"), quoted_string),
     )(i)
 }
 
+fn root(i: &str, strict: bool) -> Res<'_, JsonValue> {
+    context("json", all_consuming(|i| value(i, 0, strict)))(i)
+}
+
 pub fn json(i: &str) -> IResult<&str, JsonValue> {
-    context(
-        "json",
-        delimited(multispace0, alt((object, array)), multispace0),
-    )(i)
+    root(i, false).map_err(|e| e.map(JsonError::into_nom))
+}
+
+//解析完整的json文本，重复的键以最后一次出现的值为准
+pub fn parse(input: &str) -> Result<JsonValue, ParseError> {
+    parse_with(input, false)
+}
+
+//解析完整的json文本，对象中出现重复的键时报错
+pub fn parse_strict(input: &str) -> Result<JsonValue, ParseError> {
+    parse_with(input, true)
+}
+
+fn parse_with(input: &str, strict: bool) -> Result<JsonValue, ParseError> {
+    match root(input, strict) {
+        Ok((_, value)) => Ok(value),
+        Err(Err::Error(e)) | Err(Err::Failure(e)) => Err(e.into_parse_error(input)),
+        Err(Err::Incomplete(_)) => unreachable!("complete parsers never return Incomplete"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(message: &str, offset: usize) -> ParseError {
+        ParseError {
+            message: String::from(message),
+            offset,
+        }
+    }
+
+    #[test]
+    fn parse_requires_whole_input() {
+        assert!(matches!(parse("{}"), Ok(JsonValue::Object(map)) if map.is_empty()));
+        assert!(matches!(parse(" [1] "), Ok(JsonValue::Array(vec)) if vec.len() == 1));
+
+        let err = parse("{} junk").unwrap_err();
+        assert_eq!(err, error("unexpected trailing characters", 3));
+    }
+
+    #[test]
+    fn parse_scalar_at_root() {
+        assert!(matches!(parse("42"), Ok(JsonValue::Int(42))));
+        assert!(matches!(parse(" -1.5 "), Ok(JsonValue::Num(n)) if n == -1.5));
+        assert!(matches!(parse("\t\"hi\"\n"), Ok(JsonValue::Str(s)) if s == "hi"));
+        assert!(matches!(parse(" true"), Ok(JsonValue::Boolean(true))));
+        assert!(matches!(parse("false "), Ok(JsonValue::Boolean(false))));
+        assert!(matches!(parse("\r\n null \n"), Ok(JsonValue::Null)));
+        assert_eq!(parse("nul").unwrap_err(), error("expected value", 0));
+    }
+
+    #[test]
+    fn integers_and_floats_are_distinct() {
+        assert!(matches!(
+            parse("9007199254740993"),
+            Ok(JsonValue::Int(9007199254740993))
+        ));
+        assert!(matches!(parse("1"), Ok(JsonValue::Int(1))));
+        assert!(matches!(parse("-3"), Ok(JsonValue::Int(-3))));
+        assert!(matches!(parse("1.0"), Ok(JsonValue::Num(n)) if n == 1.0));
+        assert!(matches!(parse("1e2"), Ok(JsonValue::Num(n)) if n == 100.0));
+        assert!(matches!(parse("2.5E-1"), Ok(JsonValue::Num(n)) if n == 0.25));
+        assert!(matches!(
+            parse("99999999999999999999"),
+            Ok(JsonValue::Num(n)) if n == 1e20
+        ));
+    }
+
+    #[test]
+    fn negative_zero_keeps_its_sign() {
+        assert!(matches!(parse("-0"), Ok(JsonValue::Num(n)) if n == 0.0 && n.is_sign_negative()));
+        assert!(matches!(parse("0"), Ok(JsonValue::Int(0))));
+    }
+
+    #[test]
+    fn invalid_numbers_are_rejected() {
+        for input in &[
+            "01", "-01", "00", "01.5", "[1, 012]", "+1", ".5", "1.", "1e", "1e+", "-", "NaN",
+            "Infinity", "-inf",
+        ] {
+            assert!(parse(input).is_err(), "{} should not parse", input);
+        }
+        assert_eq!(parse("1e999").unwrap_err(), error("number out of range", 0));
+        assert!(parse("0.5").is_ok());
+        assert!(parse("-0.0").is_ok());
+        assert!(parse("0e1").is_ok());
+    }
+
+    #[test]
+    fn string_escapes_are_decoded() {
+        let value = parse(r#""line1\nline2""#).unwrap();
+        assert!(matches!(&value, JsonValue::Str(s) if s == "line1\nline2" && s.len() == 11));
+
+        let value = parse(r#""\"\\\/\b\f\n\r\t""#).unwrap();
+        assert!(matches!(&value, JsonValue::Str(s) if s == "\"\\/\u{8}\u{c}\n\r\t"));
+
+        let value = parse(r#"{"a\tb":""}"#).unwrap();
+        assert!(matches!(&value, JsonValue::Object(map) if map[0].0 == "a\tb"));
+    }
+
+    #[test]
+    fn unicode_escapes_are_decoded() {
+        assert!(matches!(parse(r#""\u00e9""#), Ok(JsonValue::Str(s)) if s == "\u{e9}"));
+        assert!(matches!(parse(r#""\u0041b""#), Ok(JsonValue::Str(s)) if s == "Ab"));
+
+        let message = "invalid unicode escape in string";
+        assert_eq!(parse(r#""\u12""#).unwrap_err(), error(message, 1));
+        assert_eq!(parse(r#""ab\u12G4""#).unwrap_err(), error(message, 3));
+        assert_eq!(parse(r#""\u+123""#).unwrap_err(), error(message, 1));
+        assert_eq!(parse(r#"{"\u":1}"#).unwrap_err(), error(message, 2));
+    }
+
+    #[test]
+    fn surrogate_pairs_are_combined() {
+        let value = parse(r#""\uD83D\uDE00""#).unwrap();
+        assert!(matches!(value, JsonValue::Str(s) if s == "\u{1F600}"));
+    }
+
+    #[test]
+    fn unpaired_surrogates_are_rejected() {
+        let message = "invalid unicode escape in string";
+        assert_eq!(parse(r#""ab\uD83D""#).unwrap_err(), error(message, 3));
+        assert_eq!(
+            parse(r#"["x", "\uD83D\u0041"]"#).unwrap_err(),
+            error(message, 7)
+        );
+        assert_eq!(parse(r#"{"\uDE00":1}"#).unwrap_err(), error(message, 2));
+    }
+
+    #[test]
+    fn json_rejects_trailing_input() {
+        let err = json("{\"a\":1}garbage").unwrap_err();
+        assert_eq!(err, Err::Error(Error::new("garbage", ErrorKind::Eof)));
+
+        assert!(matches!(json(" {} "), Ok(("", JsonValue::Object(_)))));
+        assert!(matches!(json("\n[1]\t"), Ok(("", JsonValue::Array(_)))));
+    }
+
+    #[test]
+    fn whitespace_inside_containers() {
+        assert!(matches!(parse("[ ]"), Ok(JsonValue::Array(vec)) if vec.is_empty()));
+        assert!(matches!(parse("{ }"), Ok(JsonValue::Object(map)) if map.is_empty()));
+        assert!(matches!(parse("[ 1 ,\n2 ]"), Ok(JsonValue::Array(vec)) if vec.len() == 2));
+        assert!(matches!(
+            parse("{\n  \"a\" : 1 ,\n  \"b\":2\n}"),
+            Ok(JsonValue::Object(map)) if map.len() == 2
+        ));
+    }
+
+    #[test]
+    fn parse_error_offset_inside_container() {
+        assert_eq!(parse("[1,]").unwrap_err(), error("expected value", 3));
+        assert_eq!(parse("[1 2]").unwrap_err(), error("expected ',' or ']'", 3));
+        assert_eq!(
+            parse(r#"{"a":1,}"#).unwrap_err(),
+            error("expected string key", 7)
+        );
+        assert_eq!(parse(r#"{"a" 1}"#).unwrap_err(), error("expected ':'", 5));
+        assert_eq!(
+            parse(r#"[{"a":[1,2}]"#).unwrap_err(),
+            error("expected ',' or ']'", 10)
+        );
+    }
+
+    #[test]
+    fn parse_error_messages() {
+        assert_eq!(parse("").unwrap_err(), error("unexpected end of input", 0));
+        assert_eq!(parse("[").unwrap_err(), error("unexpected end of input", 1));
+        assert_eq!(
+            parse(r#"["abc"#).unwrap_err(),
+            error("unexpected end of input", 5)
+        );
+        assert_eq!(parse("[x]").unwrap_err(), error("expected value", 1));
+        assert_eq!(
+            parse(r#"["a\x"]"#).unwrap_err(),
+            error("invalid escape sequence in string", 3)
+        );
+        assert_eq!(
+            parse("[\"a\tb\"]").unwrap_err(),
+            error("control character in string", 3)
+        );
+    }
+
+    #[test]
+    fn duplicate_keys_last_wins_by_default() {
+        let value = parse(r#"{"a":1,"b":2,"a":3}"#).unwrap();
+        assert_eq!(
+            value.as_object().unwrap(),
+            &[
+                (String::from("a"), JsonValue::Int(3)),
+                (String::from("b"), JsonValue::Int(2)),
+            ][..]
+        );
+        assert!(matches!(json(r#"{"a":1,"a":2}"#), Ok(("", _))));
+    }
+
+    #[test]
+    fn duplicate_keys_rejected_in_strict_mode() {
+        assert_eq!(
+            parse_strict(r#"{"a":1,"a":2}"#).unwrap_err(),
+            error(r#"duplicate key "a""#, 7)
+        );
+        assert_eq!(
+            parse_strict(r#"[{"b":{"a":1},"a":2,"\u0061":3}]"#).unwrap_err(),
+            error(r#"duplicate key "\u0061""#, 20)
+        );
+        assert!(parse_strict(r#"{"a":{"a":1},"b":[{"a":2}]}"#).is_ok());
+    }
+
+    #[test]
+    fn parse_recursion_limit() {
+        let nested = |n: usize| format!("{}{}", "[".repeat(n), "]".repeat(n));
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(
+            parse(&nested(MAX_DEPTH + 1)).unwrap_err(),
+            error("recursion limit exceeded", MAX_DEPTH + 1)
+        );
+        assert_eq!(
+            parse(&nested(10_000)).unwrap_err().message,
+            "recursion limit exceeded"
+        );
+
+        let objects = format!("{}{}", r#"{"a":"#.repeat(10_000), "}".repeat(10_000));
+        assert_eq!(
+            parse(&objects).unwrap_err().message,
+            "recursion limit exceeded"
+        );
+    }
 }
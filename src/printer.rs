@@ -0,0 +1,181 @@
+use std::fmt::{self, Write};
+
+use crate::parser::JsonValue;
+
+//输出紧凑格式的json文本
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonValue::Str(s) => write_str(f, s),
+            JsonValue::Boolean(b) => write!(f, "{}", b),
+            JsonValue::Null => f.write_str("null"),
+            JsonValue::Int(n) => write!(f, "{}", n),
+            JsonValue::Num(n) => write_num(f, *n),
+            JsonValue::Array(vec) => {
+                f.write_char('[')?;
+                for (index, value) in vec.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_char(']')
+            }
+            JsonValue::Object(map) => {
+                f.write_char('{')?;
+                for (index, (key, value)) in map.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_str(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+//浮点数保留小数点以区分整数，解析不会产生NaN和无穷，手动构造时输出为null
+pub(crate) fn write_num<W: Write>(w: &mut W, n: f64) -> fmt::Result {
+    if n.is_finite() {
+        write!(w, "{:?}", n)
+    } else {
+        w.write_str("null")
+    }
+}
+
+//输出带引号的字符串，转义引号、反斜杠和控制字符
+pub(crate) fn write_str<W: Write>(w: &mut W, s: &str) -> fmt::Result {
+    w.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => w.write_str("\\\"")?,
+            '\\' => w.write_str("\\\\")?,
+            '\u{8}' => w.write_str("\\b")?,
+            '\u{c}' => w.write_str("\\f")?,
+            '\n' => w.write_str("\\n")?,
+            '\r' => w.write_str("\\r")?,
+            '\t' => w.write_str("\\t")?,
+            c if c.is_ascii_control() => write!(w, "\\u{:04x}", c as u32)?,
+            c => w.write_char(c)?,
+        }
+    }
+    w.write_char('"')
+}
+
+//输出带缩进的json文本，indent为每层缩进的空格数
+pub fn to_pretty_string(value: &JsonValue, indent: usize) -> String {
+    let mut res = String::new();
+    write_pretty(&mut res, value, indent, 0).expect("writing to a String cannot fail");
+    res
+}
+
+fn write_pretty(res: &mut String, value: &JsonValue, indent: usize, level: usize) -> fmt::Result {
+    match value {
+        JsonValue::Array(vec) if !vec.is_empty() => {
+            res.push('[');
+            for (index, value) in vec.iter().enumerate() {
+                if index > 0 {
+                    res.push(',');
+                }
+                write_newline(res, indent, level + 1);
+                write_pretty(res, value, indent, level + 1)?;
+            }
+            write_newline(res, indent, level);
+            res.push(']');
+        }
+        JsonValue::Object(map) if !map.is_empty() => {
+            res.push('{');
+            for (index, (key, value)) in map.iter().enumerate() {
+                if index > 0 {
+                    res.push(',');
+                }
+                write_newline(res, indent, level + 1);
+                write_str(res, key)?;
+                res.push_str(": ");
+                write_pretty(res, value, indent, level + 1)?;
+            }
+            write_newline(res, indent, level);
+            res.push('}');
+        }
+        value => write!(res, "{}", value)?,
+    }
+
+    Ok(())
+}
+
+fn write_newline(res: &mut String, indent: usize, level: usize) {
+    res.push('\n');
+    res.push_str(&" ".repeat(indent * level));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn display_round_trip() {
+        let input = r#"[1,-2.5,1.0,-0.0,1e20,"a\"b\\c\n\u0001\u007f\u00e9",true,false,null,[],{},{"k":[{"":""}]}]"#;
+        let value = parse(input).unwrap();
+        let output = value.to_string();
+        assert_eq!(
+            output,
+            r#"[1,-2.5,1.0,-0.0,1e20,"a\"b\\c\n\u0001\u007fé",true,false,null,[],{},{"k":[{"":""}]}]"#
+        );
+        assert_eq!(parse(&output).unwrap(), value);
+    }
+
+    #[test]
+    fn object_key_order_preserved() {
+        let input = r#"{"b":1,"a":2,"c":3}"#;
+        assert_eq!(parse(input).unwrap().to_string(), input);
+
+        let value = parse(r#"{"z":{"y":1,"x":2},"a":[]}"#).unwrap();
+        assert_eq!(
+            to_pretty_string(&value, 2),
+            "{\n  \"z\": {\n    \"y\": 1,\n    \"x\": 2\n  },\n  \"a\": []\n}"
+        );
+    }
+
+    #[test]
+    fn pretty_nested() {
+        let value = parse(r#"{"a":[1,{"b":[true,null]}]}"#).unwrap();
+        let expected = "{
+  \"a\": [
+    1,
+    {
+      \"b\": [
+        true,
+        null
+      ]
+    }
+  ]
+}";
+        let output = to_pretty_string(&value, 2);
+        assert_eq!(output, expected);
+        assert_eq!(parse(&output).unwrap(), value);
+
+        let value = parse("[[1]]").unwrap();
+        assert_eq!(to_pretty_string(&value, 4), "[\n    [\n        1\n    ]\n]");
+    }
+
+    #[test]
+    fn pretty_empty_containers() {
+        assert_eq!(to_pretty_string(&parse("[]").unwrap(), 2), "[]");
+        assert_eq!(to_pretty_string(&parse("{}").unwrap(), 2), "{}");
+
+        let value = parse(r#"[[],{"a":{}}]"#).unwrap();
+        assert_eq!(
+            to_pretty_string(&value, 2),
+            "[\n  [],\n  {\n    \"a\": {}\n  }\n]"
+        );
+    }
+
+    #[test]
+    fn display_non_finite_as_null() {
+        assert_eq!(JsonValue::Num(f64::NAN).to_string(), "null");
+        assert_eq!(JsonValue::Num(f64::INFINITY).to_string(), "null");
+    }
+}
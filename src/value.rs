@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+use std::ops::Index;
+
+use crate::parser::JsonValue;
+
+//按类型取值，类型不匹配时返回None
+impl JsonValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    //整数也会转换为f64返回
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Num(n) => Some(*n),
+            JsonValue::Int(n) => Some(*n as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            JsonValue::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(vec) => Some(vec),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&[(String, JsonValue)]> {
+        match self {
+            JsonValue::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    //按键查找对象成员，非对象或键不存在时返回None
+    pub fn get<'a>(&'a self, key: &str) -> Option<&'a JsonValue> {
+        self.as_object()?
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    //按下标查找数组元素，非数组或越界时返回None
+    pub fn get_index(&self, i: usize) -> Option<&JsonValue> {
+        self.as_array()?.get(i)
+    }
+
+    //按JSON Pointer（RFC 6901）查找，""表示自身，任一段无法解析时返回None
+    pub fn pointer<'a>(&'a self, ptr: &str) -> Option<&'a JsonValue> {
+        if ptr.is_empty() {
+            return Some(self);
+        }
+        if !ptr.starts_with('/') {
+            return None;
+        }
+
+        ptr[1..].split('/').try_fold(self, |target, token| {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            match target {
+                JsonValue::Object(_) => target.get(&token),
+                JsonValue::Array(_) => target.get_index(array_index(&token)?),
+                _ => None,
+            }
+        })
+    }
+}
+
+//解析十进制数组下标，不允许前导零
+fn array_index(token: &str) -> Option<usize> {
+    if token.is_empty() || !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    if token.len() > 1 && token.starts_with('0') {
+        return None;
+    }
+    token.parse().ok()
+}
+
+static NULL: JsonValue = JsonValue::Null;
+
+//按键访问对象成员，键不存在或对Null取值时返回Null，对其他类型取值会panic
+impl Index<&str> for JsonValue {
+    type Output = JsonValue;
+
+    fn index(&self, key: &str) -> &JsonValue {
+        match self {
+            JsonValue::Object(_) => self.get(key).unwrap_or(&NULL),
+            JsonValue::Null => &NULL,
+            _ => panic!("cannot index JsonValue with key {:?}: not an object", key),
+        }
+    }
+}
+
+//按下标访问数组元素，越界或对Null取值时返回Null，对其他类型取值会panic
+impl Index<usize> for JsonValue {
+    type Output = JsonValue;
+
+    fn index(&self, index: usize) -> &JsonValue {
+        match self {
+            JsonValue::Array(_) => self.get_index(index).unwrap_or(&NULL),
+            JsonValue::Null => &NULL,
+            _ => panic!("cannot index JsonValue with {}: not an array", index),
+        }
+    }
+}
+
+//结构相等：对象忽略键的顺序，数组逐个元素比较，数字按数值比较（Int(1)与Num(1.0)相等）
+//与IEEE浮点数一致，两个Num(NaN)互不相等
+//手动构造含重复键的对象时，按每个键最后一次出现的值比较
+impl PartialEq for JsonValue {
+    fn eq(&self, other: &JsonValue) -> bool {
+        match (self, other) {
+            (JsonValue::Str(a), JsonValue::Str(b)) => a == b,
+            (JsonValue::Boolean(a), JsonValue::Boolean(b)) => a == b,
+            (JsonValue::Null, JsonValue::Null) => true,
+            (JsonValue::Int(a), JsonValue::Int(b)) => a == b,
+            (JsonValue::Num(a), JsonValue::Num(b)) => a == b,
+            (JsonValue::Int(a), JsonValue::Num(b)) | (JsonValue::Num(b), JsonValue::Int(a)) => {
+                int_eq_num(*a, *b)
+            }
+            (JsonValue::Array(a), JsonValue::Array(b)) => a == b,
+            (JsonValue::Object(a), JsonValue::Object(b)) => object_map(a) == object_map(b),
+            _ => false,
+        }
+    }
+}
+
+fn object_map(members: &[(String, JsonValue)]) -> HashMap<&str, &JsonValue> {
+    members.iter().map(|(k, v)| (k.as_str(), v)).collect()
+}
+
+//精确比较整数与浮点数，避免大整数转换为f64时丢失精度
+fn int_eq_num(a: i64, b: f64) -> bool {
+    b.fract() == 0.0 && b >= i64::MIN as f64 && b < i64::MAX as f64 && b as i64 == a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn sample() -> Vec<JsonValue> {
+        match parse(r#"["s", 1, 1.5, true, null, [0], {"a":1}]"#).unwrap() {
+            JsonValue::Array(vec) => vec,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn as_str() {
+        let vec = sample();
+        assert_eq!(vec[0].as_str(), Some("s"));
+        assert!(vec[1..].iter().all(|v| v.as_str().is_none()));
+    }
+
+    #[test]
+    fn as_f64_and_as_i64() {
+        let vec = sample();
+        assert_eq!(vec[1].as_f64(), Some(1.0));
+        assert_eq!(vec[2].as_f64(), Some(1.5));
+        assert_eq!(vec[0].as_f64(), None);
+        assert_eq!(vec[3].as_f64(), None);
+
+        assert_eq!(vec[1].as_i64(), Some(1));
+        assert_eq!(vec[2].as_i64(), None);
+        assert_eq!(vec[0].as_i64(), None);
+    }
+
+    #[test]
+    fn as_bool() {
+        let vec = sample();
+        assert_eq!(vec[3].as_bool(), Some(true));
+        assert_eq!(vec[4].as_bool(), None);
+        assert_eq!(vec[1].as_bool(), None);
+    }
+
+    #[test]
+    fn is_null() {
+        let vec = sample();
+        assert!(vec[4].is_null());
+        assert!(!vec[0].is_null());
+        assert!(!vec[5].is_null());
+    }
+
+    #[test]
+    fn as_array() {
+        let vec = sample();
+        assert!(matches!(vec[5].as_array(), Some([JsonValue::Int(0)])));
+        assert!(vec[6].as_array().is_none());
+        assert!(vec[4].as_array().is_none());
+    }
+
+    #[test]
+    fn as_object() {
+        let vec = sample();
+        assert!(matches!(vec[6].as_object(), Some([(k, JsonValue::Int(1))]) if k == "a"));
+        assert!(vec[5].as_object().is_none());
+        assert!(vec[0].as_object().is_none());
+    }
+
+    #[test]
+    fn index_present_and_missing() {
+        let value = parse(r#"{"users":[{"name":"lw"}],"n":null}"#).unwrap();
+        assert_eq!(value["users"][0]["name"].as_str(), Some("lw"));
+        assert!(value["n"].is_null());
+        assert!(value["missing"].is_null());
+        assert!(value["missing"]["deeper"][0].is_null());
+    }
+
+    #[test]
+    fn index_out_of_range() {
+        let value = parse("[1,2]").unwrap();
+        assert_eq!(value[1].as_i64(), Some(2));
+        assert!(value[2].is_null());
+        assert!(value[usize::MAX].is_null());
+    }
+
+    #[test]
+    #[should_panic(expected = "not an object")]
+    fn index_array_with_key_panics() {
+        let value = parse("[1,2]").unwrap();
+        let _ = &value["a"];
+    }
+
+    #[test]
+    #[should_panic(expected = "not an array")]
+    fn index_object_with_position_panics() {
+        let value = parse(r#"{"a":1}"#).unwrap();
+        let _ = &value[0];
+    }
+
+    #[test]
+    fn get_distinguishes_null_from_absent() {
+        let value = parse(r#"{"a":null,"b":1}"#).unwrap();
+        assert!(matches!(value.get("a"), Some(JsonValue::Null)));
+        assert_eq!(value.get("b").and_then(JsonValue::as_i64), Some(1));
+        assert!(value.get("c").is_none());
+    }
+
+    #[test]
+    fn get_on_non_object() {
+        assert!(parse(r#"["a"]"#).unwrap().get("a").is_none());
+        assert!(parse(r#""a""#).unwrap().get("a").is_none());
+        assert!(parse("null").unwrap().get("a").is_none());
+    }
+
+    #[test]
+    fn get_index() {
+        let value = parse("[null, 1]").unwrap();
+        assert!(matches!(value.get_index(0), Some(JsonValue::Null)));
+        assert_eq!(value.get_index(1).and_then(JsonValue::as_i64), Some(1));
+        assert!(value.get_index(2).is_none());
+        assert!(parse(r#"{"0":1}"#).unwrap().get_index(0).is_none());
+    }
+
+    #[test]
+    fn eq_objects_ignore_key_order() {
+        let a = parse(r#"{"a":1,"b":{"x":[true,null],"y":"s"}}"#).unwrap();
+        let b = parse(r#"{"b":{"y":"s","x":[true,null]},"a":1.0}"#).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, parse(r#"{"a":1,"b":{"x":[true,null]}}"#).unwrap());
+        assert_ne!(
+            a,
+            parse(r#"{"a":2,"b":{"x":[true,null],"y":"s"}}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn eq_arrays_element_wise() {
+        assert_eq!(parse("[1,[2]]").unwrap(), parse("[1.0,[2]]").unwrap());
+        assert_ne!(parse("[1,2]").unwrap(), parse("[2,1]").unwrap());
+        assert_ne!(parse("[1,2]").unwrap(), parse("[1,2,3]").unwrap());
+        assert_ne!(parse("[1]").unwrap(), parse(r#"["1"]"#).unwrap());
+    }
+
+    #[test]
+    fn eq_numbers_by_value() {
+        assert_eq!(JsonValue::Int(1), JsonValue::Num(1.0));
+        assert_ne!(
+            JsonValue::Int(9007199254740993),
+            JsonValue::Num(9007199254740992.0)
+        );
+        assert_ne!(JsonValue::Num(f64::NAN), JsonValue::Num(f64::NAN));
+    }
+
+    #[test]
+    fn eq_objects_with_duplicate_keys_is_symmetric() {
+        let member = |k: &str, v: i64| (String::from(k), JsonValue::Int(v));
+        let a = JsonValue::Object(vec![member("x", 1), member("x", 1)]);
+        let b = JsonValue::Object(vec![member("x", 1), member("y", 2)]);
+        assert_ne!(a, b);
+        assert_ne!(b, a);
+
+        let c = JsonValue::Object(vec![member("x", 2), member("x", 1)]);
+        let d = JsonValue::Object(vec![member("x", 1)]);
+        assert_eq!(c, d);
+        assert_eq!(d, c);
+    }
+
+    #[test]
+    fn clone_nested() {
+        let value =
+            parse(r#"{"a":[1,{"b":[[null,"x"],{"c":2.5}]}],"d":{"e":{"f":[true]}}}"#).unwrap();
+        let mut copy = value.clone();
+        assert_eq!(copy, value);
+
+        if let JsonValue::Object(members) = &mut copy {
+            members[0].1 = JsonValue::Null;
+        }
+        assert_ne!(copy, value);
+        assert_eq!(value["a"][1]["b"][0][1].as_str(), Some("x"));
+    }
+
+    #[test]
+    fn pointer_nested() {
+        let value = parse(r#"{"foo":["bar",{"baz":[1,2]}],"":0}"#).unwrap();
+        assert_eq!(value.pointer(""), Some(&value));
+        assert_eq!(
+            value.pointer("/foo/0").and_then(JsonValue::as_str),
+            Some("bar")
+        );
+        assert_eq!(value.pointer("/foo/1/baz/1"), Some(&JsonValue::Int(2)));
+        assert_eq!(value.pointer("/"), Some(&JsonValue::Int(0)));
+        assert_eq!(value.pointer("foo"), None);
+        assert_eq!(value.pointer("/missing"), None);
+        assert_eq!(value.pointer("/foo/0/x"), None);
+    }
+
+    #[test]
+    fn pointer_escaped_tokens() {
+        let value = parse(r#"{"a/b":1,"m~n":2,"~1":3}"#).unwrap();
+        assert_eq!(value.pointer("/a~1b"), Some(&JsonValue::Int(1)));
+        assert_eq!(value.pointer("/m~0n"), Some(&JsonValue::Int(2)));
+        assert_eq!(value.pointer("/~01"), Some(&JsonValue::Int(3)));
+        assert_eq!(value.pointer("/a/b"), None);
+    }
+
+    #[test]
+    fn pointer_array_indices() {
+        let value = parse("[10, 20]").unwrap();
+        assert_eq!(value.pointer("/1"), Some(&JsonValue::Int(20)));
+        assert_eq!(value.pointer("/2"), None);
+        assert_eq!(value.pointer("/01"), None);
+        assert_eq!(value.pointer("/-"), None);
+        assert_eq!(value.pointer("/+1"), None);
+        assert_eq!(value.pointer("/"), None);
+        assert_eq!(value.pointer("/99999999999999999999999"), None);
+    }
+}